@@ -0,0 +1,123 @@
+use chrono::Duration;
+use std::str::FromStr;
+
+/// Arguments to `/settemp`: a zone, and optionally the target temperature to
+/// set right away. When `target_temp` is `None`, the caller should fall back
+/// to the interactive dialogue.
+#[derive(Clone)]
+pub struct SetTempArgs {
+    pub zone: String,
+    pub target_temp: Option<f64>,
+}
+
+impl FromStr for SetTempArgs {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let zone = parts
+            .next()
+            .ok_or("Usage: /settemp <zone> [target temp]")?
+            .to_string();
+
+        let target_temp = match parts.next() {
+            Some(temp) => Some(
+                temp.parse::<f64>()
+                    .map_err(|_| format!("\"{temp}\" is not a valid temperature"))?,
+            ),
+            None => None,
+        };
+
+        if parts.next().is_some() {
+            return Err("Usage: /settemp <zone> [target temp]".to_string());
+        }
+
+        Ok(Self { zone, target_temp })
+    }
+}
+
+/// A single required zone-name argument, e.g. for `/on`, `/off`,
+/// `/status`, `/schedule`, `/addslot` and `/clearschedule`. Without this, a
+/// command sent with no argument would parse to an empty `zone` and
+/// silently auto-vivify a phantom `""` zone.
+#[derive(Clone)]
+pub struct ZoneArg(pub String);
+
+impl FromStr for ZoneArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let zone = s.trim();
+        if zone.is_empty() {
+            return Err("Usage: <command> <zone>".to_string());
+        }
+
+        Ok(Self(zone.to_string()))
+    }
+}
+
+/// Arguments to `/override`, e.g. `"living_room 22 90m"`: set `zone`'s
+/// target temperature to `target_temp` for `duration`, then let the schedule
+/// take back over.
+#[derive(Clone)]
+pub struct OverrideArgs {
+    pub zone: String,
+    pub target_temp: f64,
+    pub duration: Duration,
+}
+
+impl FromStr for OverrideArgs {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let zone = parts
+            .next()
+            .ok_or("Usage: /override <zone> <target temp> <duration, e.g. 90m>")?
+            .to_string();
+        let target_temp = parts
+            .next()
+            .ok_or("Usage: /override <zone> <target temp> <duration, e.g. 90m>")?
+            .parse::<f64>()
+            .map_err(|_| "invalid target temperature".to_string())?;
+        let duration = parts
+            .next()
+            .ok_or("Usage: /override <zone> <target temp> <duration, e.g. 90m>")?
+            .parse::<DurationArg>()
+            .map(|d| d.0)?;
+
+        if parts.next().is_some() {
+            return Err("Usage: /override <zone> <target temp> <duration, e.g. 90m>".to_string());
+        }
+
+        Ok(Self {
+            zone,
+            target_temp,
+            duration,
+        })
+    }
+}
+
+/// Parses durations like `"90m"`, `"2h"` or `"30s"`.
+struct DurationArg(Duration);
+
+impl FromStr for DurationArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let split_at = s.len().saturating_sub(1);
+        let (value, unit) = s.split_at(split_at);
+        let value = value
+            .parse::<i64>()
+            .map_err(|_| format!("invalid duration \"{s}\""))?;
+
+        let duration = match unit {
+            "s" => Duration::seconds(value),
+            "m" => Duration::minutes(value),
+            "h" => Duration::hours(value),
+            _ => return Err(format!("invalid duration unit in \"{s}\", expected s/m/h")),
+        };
+
+        Ok(Self(duration))
+    }
+}
@@ -0,0 +1,79 @@
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+fn default_port() -> String {
+    "8080".to_string()
+}
+
+fn default_target_temp() -> f64 {
+    21.0
+}
+
+fn default_stale_after_secs() -> u64 {
+    15 * 60
+}
+
+/// Bot configuration loaded from a TOML file. A handful of fields can still
+/// be overridden via environment variables, for ops tweaks that shouldn't
+/// require editing and redeploying the file.
+#[derive(Deserialize)]
+pub struct Config {
+    pub bot_token: String,
+    #[serde(default = "default_port")]
+    pub port: String,
+    pub authorized_users: Vec<i64>,
+    #[serde(default)]
+    pub admin_users: Vec<i64>,
+    #[serde(default = "default_target_temp")]
+    pub default_target_temp: f64,
+    #[serde(default = "default_stale_after_secs")]
+    pub stale_after_secs: u64,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read config file {}: {e}", path.display()))?;
+
+        let mut config: Config = toml::from_str(&contents)
+            .map_err(|e| format!("failed to parse config file {}: {e}", path.display()))?;
+
+        if let Ok(bot_token) = std::env::var("BOT_TOKEN") {
+            config.bot_token = bot_token;
+        }
+        if let Ok(port) = std::env::var("PORT") {
+            config.port = port;
+        }
+        if let Ok(authorized_users) = std::env::var("AUTHORIZED_USER_IDS") {
+            config.authorized_users = authorized_users
+                .split(' ')
+                .map(|s| {
+                    s.parse::<i64>()
+                        .map_err(|e| format!("invalid AUTHORIZED_USER_IDS: {e}"))
+                })
+                .collect::<Result<_, _>>()?;
+        }
+        if let Ok(admin_users) = std::env::var("ADMIN_USER_IDS") {
+            config.admin_users = admin_users
+                .split(' ')
+                .map(|s| {
+                    s.parse::<i64>()
+                        .map_err(|e| format!("invalid ADMIN_USER_IDS: {e}"))
+                })
+                .collect::<Result<_, _>>()?;
+        }
+        if let Ok(default_target_temp) = std::env::var("DEFAULT_TARGET_TEMP") {
+            config.default_target_temp = default_target_temp
+                .parse()
+                .map_err(|e| format!("invalid DEFAULT_TARGET_TEMP: {e}"))?;
+        }
+        if let Ok(stale_after_secs) = std::env::var("STALE_AFTER_SECS") {
+            config.stale_after_secs = stale_after_secs
+                .parse()
+                .map_err(|e| format!("invalid STALE_AFTER_SECS: {e}"))?;
+        }
+
+        Ok(config)
+    }
+}
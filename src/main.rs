@@ -1,11 +1,30 @@
+mod command_args;
+mod config;
+mod metrics;
+mod schedule;
+mod scheduler;
 mod server;
+mod storage;
+mod zone;
 
+use crate::command_args::{OverrideArgs, SetTempArgs, ZoneArg};
+use crate::config::Config;
+use crate::schedule::parse_slot;
+use crate::scheduler::run_scheduler;
 use crate::server::start_server;
+use crate::storage::Storage;
+use crate::zone::Zone;
+use chrono::{DateTime, Local};
+use clap::Parser;
 use log::{error, info};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::env;
+use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::{Duration, SystemTime};
-use teloxide::dispatching::dialogue::InMemStorage;
+use std::time::SystemTime;
+use teloxide::dispatching::dialogue::serializer::Json;
+use teloxide::dispatching::dialogue::SqliteStorage;
 use teloxide::{
     dispatching::{dialogue, UpdateHandler},
     prelude::*,
@@ -13,14 +32,26 @@ use teloxide::{
 };
 use tokio::sync::Mutex;
 
-type MyDialogue = Dialogue<State, InMemStorage<State>>;
+const DEFAULT_DATABASE_PATH: &str = "heating.sqlite";
+const DEFAULT_HYSTERESIS_BAND: f64 = 0.5;
+
+/// Heating Control bot.
+#[derive(Parser)]
+struct Cli {
+    /// Path to the TOML configuration file.
+    #[arg(long, default_value = "config.toml")]
+    config: PathBuf,
+}
+
+type MyDialogue = Dialogue<State, SqliteStorage<Json>>;
 type HandlerResult = Result<(), Box<dyn std::error::Error + Send + Sync>>;
 
-#[derive(Clone, Default)]
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub enum State {
     #[default]
     Initial,
-    ReceiveTemp,
+    ReceiveTemp(String),
+    ReceiveSlot(String),
 }
 
 /// These commands are supported:
@@ -28,33 +59,111 @@ pub enum State {
 #[command(rename_rule = "lowercase")]
 enum Command {
     Help,
-    Status,
-    SetTemp,
-    On,
-    Off,
+    Status(ZoneArg),
+    SetTemp(SetTempArgs),
+    Override(OverrideArgs),
+    On(ZoneArg),
+    Off(ZoneArg),
+    Schedule(ZoneArg),
+    AddSlot(ZoneArg),
+    ClearSchedule(ZoneArg),
     Cancel,
 }
 
+/// These commands are restricted to admins:
+#[derive(BotCommands, Clone)]
+#[command(rename_rule = "lowercase")]
+enum AdminCommand {
+    AddUser(i64),
+    RemoveUser(i64),
+    SetStale(u64),
+    Reboot,
+}
+
 pub struct HeatingState {
-    pub target_temp: f64,
-    pub current_temp: f64,
-    pub current_temp_reported_at: SystemTime,
-    pub heating_switch_is_on: bool,
+    pub zones: HashMap<String, Zone>,
+    default_target_temp: f64,
+    hysteresis_band: f64,
+    stale_after_secs: u64,
+    storage: Storage,
 }
 
 impl HeatingState {
-    pub fn heating_is_on(&self) -> bool {
-        if SystemTime::now()
-            .duration_since(self.current_temp_reported_at)
-            .unwrap()
-            > Duration::from_secs(15 * 60)
-        {
-            return false;
-        }
+    /// Returns the named zone, creating it with the configured default
+    /// target temperature on first use (e.g. the first sensor reading it posts).
+    pub fn zone_mut(&mut self, name: &str) -> &mut Zone {
+        let default_target_temp = self.default_target_temp;
+        self.zones
+            .entry(name.to_string())
+            .or_insert_with(|| Zone::new(default_target_temp))
+    }
+
+    /// Updates a zone's target temperature, recomputes its on/off decision
+    /// and persists the target so it survives a restart.
+    pub async fn set_zone_target_temp(&mut self, zone: &str, target_temp: f64) {
+        let band = self.hysteresis_band;
+        let stale_after_secs = self.stale_after_secs;
+        let zone_state = self.zone_mut(zone);
+        zone_state.target_temp = target_temp;
+        zone_state.update_decision(band, stale_after_secs);
+        self.persist_zone(zone).await;
+    }
+
+    /// Updates a zone's heating switch, recomputes its on/off decision and
+    /// persists the switch so it survives a restart.
+    pub async fn set_zone_heating_switch(&mut self, zone: &str, is_on: bool) {
+        let band = self.hysteresis_band;
+        let stale_after_secs = self.stale_after_secs;
+        let zone_state = self.zone_mut(zone);
+        zone_state.heating_switch_is_on = is_on;
+        zone_state.update_decision(band, stale_after_secs);
+        self.persist_zone(zone).await;
+    }
+
+    /// Sets a manual target temperature for a zone that expires at
+    /// `expires_at`, after which the schedule (if any) takes back over.
+    pub async fn set_zone_manual_override(
+        &mut self,
+        zone: &str,
+        target_temp: f64,
+        expires_at: Option<DateTime<Local>>,
+    ) {
+        self.zone_mut(zone).override_until = expires_at;
+        self.set_zone_target_temp(zone, target_temp).await;
+    }
+
+    /// Recomputes a zone's on/off decision, e.g. after a fresh sensor reading.
+    pub fn update_zone_decision(&mut self, zone: &str) {
+        let band = self.hysteresis_band;
+        let stale_after_secs = self.stale_after_secs;
+        self.zone_mut(zone).update_decision(band, stale_after_secs);
+    }
+
+    /// Updates the staleness threshold used by every zone's on/off decision
+    /// and persists it so it survives a restart.
+    pub async fn set_stale_after_secs(&mut self, stale_after_secs: u64) {
+        self.stale_after_secs = stale_after_secs;
+        self.storage
+            .save_setting("stale_after_secs", &stale_after_secs.to_string())
+            .await;
+    }
+
+    /// Persists the current authorized-user list so `/adduser`/`/removeuser`
+    /// survive a restart (e.g. the admin-only `/reboot`).
+    pub async fn persist_authorized_users(&self, authorized_users: &[i64]) {
+        let csv = authorized_users
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        self.storage.save_setting("authorized_users", &csv).await;
+    }
 
-        match self.heating_switch_is_on && self.current_temp < self.target_temp {
-            true => true,
-            false => false,
+    async fn persist_zone(&self, zone: &str) {
+        if let Some(zone_state) = self.zones.get(zone) {
+            self.storage
+                .save_zone(zone, zone_state.target_temp, zone_state.heating_switch_is_on)
+                .await;
         }
     }
 }
@@ -64,30 +173,86 @@ async fn main() {
     env_logger::init();
     info!("Starting Heating Control bot...");
 
-    let bot = Bot::from_env();
-
-    let webhook_port = env::var("PORT").unwrap();
-
-    let authorized_users: Vec<i64> = env::var("AUTHORIZED_USER_IDS")
-        .unwrap()
-        .split(' ')
-        .map(|s| s.parse::<i64>().unwrap())
+    let cli = Cli::parse();
+    let config = Config::load(&cli.config).unwrap_or_else(|e| {
+        eprintln!("Failed to load configuration: {e}");
+        std::process::exit(1);
+    });
+
+    let bot = Bot::new(&config.bot_token);
+    let webhook_port = config.port.clone();
+    let admin_users = config.admin_users.clone();
+
+    let database_path =
+        env::var("DATABASE_PATH").unwrap_or_else(|_| DEFAULT_DATABASE_PATH.to_string());
+
+    let hysteresis_band = env::var("HYSTERESIS_BAND")
+        .ok()
+        .and_then(|s| s.parse::<f64>().ok())
+        .unwrap_or(DEFAULT_HYSTERESIS_BAND);
+
+    let storage = Storage::open(&database_path)
+        .await
+        .expect("failed to open the SQLite database");
+    let zones = storage
+        .load_zones()
+        .await
+        .expect("failed to load persisted zones")
+        .into_iter()
+        .map(|(name, (target_temp, heating_switch_is_on))| {
+            let mut zone = Zone::new(target_temp);
+            zone.heating_switch_is_on = heating_switch_is_on;
+            (name, zone)
+        })
         .collect();
 
+    // Runtime edits to these two made via `/adduser`, `/removeuser` and
+    // `/setstale` are persisted in `storage`, which takes precedence over the
+    // config file so they survive a `/reboot`.
+    let authorized_users = match storage
+        .load_setting("authorized_users")
+        .await
+        .expect("failed to load persisted authorized users")
+    {
+        Some(csv) => csv
+            .split(',')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse::<i64>().expect("corrupt persisted authorized_users"))
+            .collect(),
+        None => config.authorized_users.clone(),
+    };
+    let authorized_users = Arc::new(Mutex::new(authorized_users));
+
+    let stale_after_secs = match storage
+        .load_setting("stale_after_secs")
+        .await
+        .expect("failed to load persisted stale_after_secs")
+    {
+        Some(secs) => secs.parse().expect("corrupt persisted stale_after_secs"),
+        None => config.stale_after_secs,
+    };
+
     let heating_state = Arc::new(Mutex::new(HeatingState {
-        target_temp: 21.0,
-        current_temp: 0.0,
-        current_temp_reported_at: SystemTime::now(),
-        heating_switch_is_on: false,
+        zones,
+        default_target_temp: config.default_target_temp,
+        hysteresis_band,
+        stale_after_secs,
+        storage,
     }));
 
+    let dialogue_storage = SqliteStorage::open(&database_path, Json)
+        .await
+        .expect("failed to open the dialogue SQLite storage");
+
     tokio::spawn(start_server(webhook_port, heating_state.clone()));
+    tokio::spawn(run_scheduler(heating_state.clone()));
 
     Dispatcher::builder(bot, schema())
         .dependencies(dptree::deps![
-            InMemStorage::<State>::new(),
+            dialogue_storage,
             heating_state,
-            authorized_users
+            authorized_users,
+            admin_users
         ])
         .enable_ctrlc_handler()
         .build()
@@ -102,20 +267,33 @@ fn schema() -> UpdateHandler<Box<dyn std::error::Error + Send + Sync + 'static>>
         .branch(
             case![State::Initial]
                 .branch(case![Command::Help].endpoint(help))
-                .branch(case![Command::Status].endpoint(status))
-                .branch(case![Command::SetTemp].endpoint(set_temp))
-                .branch(case![Command::On].endpoint(set_heating_on))
-                .branch(case![Command::Off].endpoint(set_heating_off)),
+                .branch(case![Command::Status(zone)].endpoint(status))
+                .branch(case![Command::SetTemp(args)].endpoint(set_temp))
+                .branch(case![Command::Override(args)].endpoint(set_override))
+                .branch(case![Command::On(zone)].endpoint(set_heating_on))
+                .branch(case![Command::Off(zone)].endpoint(set_heating_off))
+                .branch(case![Command::Schedule(zone)].endpoint(schedule_cmd))
+                .branch(case![Command::AddSlot(zone)].endpoint(add_slot))
+                .branch(case![Command::ClearSchedule(zone)].endpoint(clear_schedule)),
         )
         .branch(case![Command::Cancel].endpoint(cancel));
 
+    let admin_command_handler = teloxide::filter_command::<AdminCommand, _>()
+        .chain(dptree::filter_async(check_is_admin))
+        .branch(case![AdminCommand::AddUser(user_id)].endpoint(add_user))
+        .branch(case![AdminCommand::RemoveUser(user_id)].endpoint(remove_user))
+        .branch(case![AdminCommand::SetStale(secs)].endpoint(set_stale))
+        .branch(case![AdminCommand::Reboot].endpoint(reboot));
+
     let message_handler = Update::filter_message()
         .chain(dptree::filter_async(check_valid_user))
         .branch(command_handler)
-        .branch(case![State::ReceiveTemp].endpoint(receive_temp))
+        .branch(admin_command_handler)
+        .branch(case![State::ReceiveTemp(zone)].endpoint(receive_temp))
+        .branch(case![State::ReceiveSlot(zone)].endpoint(receive_slot))
         .branch(dptree::endpoint(invalid_state));
 
-    dialogue::enter::<Update, InMemStorage<State>, State, _>().branch(message_handler)
+    dialogue::enter::<Update, SqliteStorage<Json>, State, _>().branch(message_handler)
 }
 
 async fn help(bot: Bot, msg: Message) -> HandlerResult {
@@ -140,29 +318,41 @@ async fn invalid_state(bot: Bot, msg: Message) -> HandlerResult {
     Ok(())
 }
 
-async fn status(bot: Bot, msg: Message, heating_state: Arc<Mutex<HeatingState>>) -> HandlerResult {
+async fn status(
+    bot: Bot,
+    msg: Message,
+    heating_state: Arc<Mutex<HeatingState>>,
+    ZoneArg(zone): ZoneArg,
+) -> HandlerResult {
     let state = heating_state.lock().await;
 
-    let switch_on_off = match state.heating_switch_is_on {
+    let Some(zone_state) = state.zones.get(&zone) else {
+        bot.send_message(msg.chat.id, format!("Unknown zone \"{zone}\""))
+            .await?;
+        return Ok(());
+    };
+
+    let switch_on_off = match zone_state.heating_switch_is_on {
         true => "ON",
         false => "OFF",
     };
-    let heating_on_off = match state.heating_is_on() {
+    let heating_on_off = match zone_state.heating_is_on() {
         true => "ON",
         false => "OFF",
     };
-    let temp_report_delay = SystemTime::now().duration_since(state.current_temp_reported_at)?;
+    let temp_report_delay =
+        SystemTime::now().duration_since(zone_state.current_temp_reported_at)?;
     bot.send_message(
         msg.chat.id,
         format!(
             "\
-        Current status: \n\
+        Current status for {zone}: \n\
          * Switch is {switch_on_off}\n\
          * Target temperature is {}\n\
          * Current temperature is {} ({} secs ago)\n\
          Meaning heating is currently: {heating_on_off}",
-            state.target_temp,
-            state.current_temp,
+            zone_state.target_temp,
+            zone_state.current_temp,
             temp_report_delay.as_secs(),
         ),
     )
@@ -171,10 +361,76 @@ async fn status(bot: Bot, msg: Message, heating_state: Arc<Mutex<HeatingState>>)
     Ok(())
 }
 
-async fn set_temp(bot: Bot, dialogue: MyDialogue, msg: Message) -> HandlerResult {
-    bot.send_message(msg.chat.id, "Enter the target temperature to set up")
-        .await?;
-    dialogue.update(State::ReceiveTemp).await?;
+async fn set_temp(
+    bot: Bot,
+    dialogue: MyDialogue,
+    msg: Message,
+    heating_state: Arc<Mutex<HeatingState>>,
+    args: SetTempArgs,
+) -> HandlerResult {
+    let mut state = heating_state.lock().await;
+
+    if !state.zones.contains_key(&args.zone) {
+        bot.send_message(msg.chat.id, format!("Unknown zone \"{}\"", args.zone))
+            .await?;
+        return Ok(());
+    }
+
+    match args.target_temp {
+        Some(target_temp) => {
+            let expires_at = state
+                .zone_mut(&args.zone)
+                .schedule
+                .next_transition(Local::now());
+            state
+                .set_zone_manual_override(&args.zone, target_temp, expires_at)
+                .await;
+            drop(state);
+
+            bot.send_message(
+                msg.chat.id,
+                format!("Target temperature for {} set to {target_temp}", args.zone),
+            )
+            .await?;
+        }
+        None => {
+            drop(state);
+
+            bot.send_message(
+                msg.chat.id,
+                format!("Enter the target temperature for {}", args.zone),
+            )
+            .await?;
+            dialogue.update(State::ReceiveTemp(args.zone)).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn set_override(
+    bot: Bot,
+    msg: Message,
+    heating_state: Arc<Mutex<HeatingState>>,
+    args: OverrideArgs,
+) -> HandlerResult {
+    let expires_at = Local::now() + args.duration;
+
+    heating_state
+        .lock()
+        .await
+        .set_zone_manual_override(&args.zone, args.target_temp, Some(expires_at))
+        .await;
+
+    bot.send_message(
+        msg.chat.id,
+        format!(
+            "Target temperature for {} overridden to {} until {}",
+            args.zone,
+            args.target_temp,
+            expires_at.format("%H:%M")
+        ),
+    )
+    .await?;
     Ok(())
 }
 
@@ -183,6 +439,7 @@ async fn receive_temp(
     dialogue: MyDialogue,
     msg: Message,
     heating_state: Arc<Mutex<HeatingState>>,
+    zone: String,
 ) -> HandlerResult {
     match msg.text().map(ToOwned::to_owned) {
         Some(temperature) => {
@@ -200,11 +457,16 @@ async fn receive_temp(
                 }
             };
 
-            heating_state.lock().await.target_temp = temperature;
+            let mut state = heating_state.lock().await;
+            let expires_at = state.zone_mut(&zone).schedule.next_transition(Local::now());
+            state
+                .set_zone_manual_override(&zone, temperature, expires_at)
+                .await;
+            drop(state);
 
             bot.send_message(
                 msg.chat.id,
-                format!("Target temperature set to {temperature}"),
+                format!("Target temperature for {zone} set to {temperature}"),
             )
             .await?;
 
@@ -225,10 +487,21 @@ async fn set_heating_on(
     bot: Bot,
     msg: Message,
     heating_state: Arc<Mutex<HeatingState>>,
+    ZoneArg(zone): ZoneArg,
 ) -> HandlerResult {
-    heating_state.lock().await.heating_switch_is_on = true;
+    let mut state = heating_state.lock().await;
+
+    if !state.zones.contains_key(&zone) {
+        bot.send_message(msg.chat.id, format!("Unknown zone \"{zone}\""))
+            .await?;
+        return Ok(());
+    }
+
+    state.set_zone_heating_switch(&zone, true).await;
+    drop(state);
 
-    bot.send_message(msg.chat.id, "Heating set to ON").await?;
+    bot.send_message(msg.chat.id, format!("Heating set to ON for {zone}"))
+        .await?;
     Ok(())
 }
 
@@ -236,23 +509,225 @@ async fn set_heating_off(
     bot: Bot,
     msg: Message,
     heating_state: Arc<Mutex<HeatingState>>,
+    ZoneArg(zone): ZoneArg,
 ) -> HandlerResult {
-    heating_state.lock().await.heating_switch_is_on = false;
+    let mut state = heating_state.lock().await;
 
-    bot.send_message(msg.chat.id, "Heating set to OFF").await?;
+    if !state.zones.contains_key(&zone) {
+        bot.send_message(msg.chat.id, format!("Unknown zone \"{zone}\""))
+            .await?;
+        return Ok(());
+    }
+
+    state.set_zone_heating_switch(&zone, false).await;
+    drop(state);
+
+    bot.send_message(msg.chat.id, format!("Heating set to OFF for {zone}"))
+        .await?;
     Ok(())
 }
 
-async fn check_valid_user(bot: Bot, msg: Message, authorized_users: Vec<i64>) -> bool {
-    match authorized_users.contains(&msg.chat.id.0) {
-        true => true,
-        false => {
-            error!(
-                "Unauthorized user tried to send message: {}",
-                &msg.chat.id.0
-            );
-            let _ = bot.send_message(msg.chat.id, "Unauthorized user").await;
-            false
+async fn schedule_cmd(
+    bot: Bot,
+    msg: Message,
+    heating_state: Arc<Mutex<HeatingState>>,
+    ZoneArg(zone): ZoneArg,
+) -> HandlerResult {
+    let state = heating_state.lock().await;
+
+    let Some(zone_state) = state.zones.get(&zone) else {
+        bot.send_message(msg.chat.id, format!("Unknown zone \"{zone}\""))
+            .await?;
+        return Ok(());
+    };
+
+    if zone_state.schedule.slots.is_empty() {
+        bot.send_message(
+            msg.chat.id,
+            format!("No schedule slots configured for {zone}."),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let slots = zone_state
+        .schedule
+        .slots
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    bot.send_message(msg.chat.id, format!("Schedule for {zone}:\n{slots}"))
+        .await?;
+    Ok(())
+}
+
+async fn add_slot(
+    bot: Bot,
+    dialogue: MyDialogue,
+    msg: Message,
+    heating_state: Arc<Mutex<HeatingState>>,
+    ZoneArg(zone): ZoneArg,
+) -> HandlerResult {
+    if !heating_state.lock().await.zones.contains_key(&zone) {
+        bot.send_message(msg.chat.id, format!("Unknown zone \"{zone}\""))
+            .await?;
+        return Ok(());
+    }
+
+    bot.send_message(
+        msg.chat.id,
+        format!(
+            "Send the slot for {zone} as \"<weekday> <HH:MM> <target temp>\", e.g. \"Mon 07:00 21.5\""
+        ),
+    )
+    .await?;
+    dialogue.update(State::ReceiveSlot(zone)).await?;
+    Ok(())
+}
+
+async fn receive_slot(
+    bot: Bot,
+    dialogue: MyDialogue,
+    msg: Message,
+    heating_state: Arc<Mutex<HeatingState>>,
+    zone: String,
+) -> HandlerResult {
+    match msg.text().map(parse_slot) {
+        Some(Some(slot)) => {
+            let text = slot.to_string();
+            heating_state
+                .lock()
+                .await
+                .zone_mut(&zone)
+                .schedule
+                .slots
+                .push(slot);
+
+            bot.send_message(msg.chat.id, format!("Added slot for {zone}: {text}"))
+                .await?;
+            dialogue.exit().await?;
         }
+        _ => {
+            bot.send_message(
+                msg.chat.id,
+                "That's not a valid slot. Send it as \"<weekday> <HH:MM> <target temp>\", e.g. \"Mon 07:00 21.5\"",
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+async fn clear_schedule(
+    bot: Bot,
+    msg: Message,
+    heating_state: Arc<Mutex<HeatingState>>,
+    ZoneArg(zone): ZoneArg,
+) -> HandlerResult {
+    let mut state = heating_state.lock().await;
+
+    if !state.zones.contains_key(&zone) {
+        bot.send_message(msg.chat.id, format!("Unknown zone \"{zone}\""))
+            .await?;
+        return Ok(());
+    }
+
+    state.zone_mut(&zone).schedule.slots.clear();
+    drop(state);
+
+    bot.send_message(msg.chat.id, format!("Schedule cleared for {zone}."))
+        .await?;
+    Ok(())
+}
+
+/// Admins are implicitly valid users even before they're added to
+/// `authorized_users`, so that an admin can bootstrap the authorized-user
+/// list at runtime via `/adduser`.
+async fn check_valid_user(
+    bot: Bot,
+    msg: Message,
+    authorized_users: Arc<Mutex<Vec<i64>>>,
+    admin_users: Vec<i64>,
+) -> bool {
+    let is_valid = admin_users.contains(&msg.chat.id.0)
+        || authorized_users.lock().await.contains(&msg.chat.id.0);
+
+    if !is_valid {
+        error!(
+            "Unauthorized user tried to send message: {}",
+            &msg.chat.id.0
+        );
+        let _ = bot.send_message(msg.chat.id, "Unauthorized user").await;
+    }
+
+    is_valid
+}
+
+async fn check_is_admin(msg: Message, admin_users: Vec<i64>) -> bool {
+    admin_users.contains(&msg.chat.id.0)
+}
+
+async fn add_user(
+    bot: Bot,
+    msg: Message,
+    heating_state: Arc<Mutex<HeatingState>>,
+    authorized_users: Arc<Mutex<Vec<i64>>>,
+    user_id: i64,
+) -> HandlerResult {
+    let mut authorized_users = authorized_users.lock().await;
+
+    if authorized_users.contains(&user_id) {
+        bot.send_message(msg.chat.id, format!("User {user_id} is already authorized."))
+            .await?;
+    } else {
+        authorized_users.push(user_id);
+        heating_state
+            .lock()
+            .await
+            .persist_authorized_users(&authorized_users)
+            .await;
+        bot.send_message(msg.chat.id, format!("User {user_id} authorized."))
+            .await?;
     }
+    Ok(())
+}
+
+async fn remove_user(
+    bot: Bot,
+    msg: Message,
+    heating_state: Arc<Mutex<HeatingState>>,
+    authorized_users: Arc<Mutex<Vec<i64>>>,
+    user_id: i64,
+) -> HandlerResult {
+    let mut authorized_users = authorized_users.lock().await;
+    authorized_users.retain(|&id| id != user_id);
+    heating_state
+        .lock()
+        .await
+        .persist_authorized_users(&authorized_users)
+        .await;
+
+    bot.send_message(msg.chat.id, format!("User {user_id} removed."))
+        .await?;
+    Ok(())
+}
+
+async fn set_stale(
+    bot: Bot,
+    msg: Message,
+    heating_state: Arc<Mutex<HeatingState>>,
+    secs: u64,
+) -> HandlerResult {
+    heating_state.lock().await.set_stale_after_secs(secs).await;
+
+    bot.send_message(msg.chat.id, format!("Stale-after threshold set to {secs}s."))
+        .await?;
+    Ok(())
+}
+
+async fn reboot(bot: Bot, msg: Message) -> HandlerResult {
+    bot.send_message(msg.chat.id, "Rebooting...").await?;
+    std::process::exit(0);
 }
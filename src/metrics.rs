@@ -0,0 +1,94 @@
+use crate::HeatingState;
+use std::fmt::Write as _;
+use std::time::SystemTime;
+
+/// Renders the current heating state as Prometheus text-format metrics, one
+/// sample per zone.
+pub fn render(heating_state: &HeatingState) -> String {
+    let mut out = String::new();
+
+    push_metric(
+        &mut out,
+        "heating_current_temp_celsius",
+        "gauge",
+        "Last reported temperature.",
+        heating_state
+            .zones
+            .iter()
+            .map(|(zone, state)| (zone, state.current_temp)),
+    );
+
+    push_metric(
+        &mut out,
+        "heating_target_temp_celsius",
+        "gauge",
+        "Target temperature.",
+        heating_state
+            .zones
+            .iter()
+            .map(|(zone, state)| (zone, state.target_temp)),
+    );
+
+    push_metric(
+        &mut out,
+        "heating_is_on",
+        "gauge",
+        "Whether heating is currently commanded on (1) or off (0).",
+        heating_state
+            .zones
+            .iter()
+            .map(|(zone, state)| (zone, if state.heating_is_on() { 1.0 } else { 0.0 })),
+    );
+
+    push_metric(
+        &mut out,
+        "heating_seconds_since_last_reading",
+        "gauge",
+        "Seconds since the last sensor reading.",
+        heating_state.zones.iter().map(|(zone, state)| {
+            let secs = SystemTime::now()
+                .duration_since(state.current_temp_reported_at)
+                .unwrap_or_default()
+                .as_secs_f64();
+            (zone, secs)
+        }),
+    );
+
+    push_metric(
+        &mut out,
+        "heating_runtime_seconds_total",
+        "counter",
+        "Cumulative seconds heating has been commanded on.",
+        heating_state
+            .zones
+            .iter()
+            .map(|(zone, state)| (zone, state.heating_runtime_secs())),
+    );
+
+    out
+}
+
+fn push_metric<'a>(
+    out: &mut String,
+    name: &str,
+    metric_type: &str,
+    help: &str,
+    samples: impl Iterator<Item = (&'a String, f64)>,
+) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {metric_type}");
+    for (zone, value) in samples {
+        let zone = escape_label_value(zone);
+        let _ = writeln!(out, "{name}{{zone=\"{zone}\"}} {value}");
+    }
+}
+
+/// Escapes a string for use as a Prometheus label value, per the exposition
+/// format: backslashes, double quotes and newlines must be escaped, or a
+/// zone name containing one of them would break the line for every metric.
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,119 @@
+use chrono::{DateTime, Datelike, Local, NaiveTime, Timelike, Weekday};
+use std::fmt;
+
+/// A single entry in a weekly heating schedule: from `start_time` on
+/// `weekday` onward, the target temperature is `target_temp`, until the next
+/// entry (by start time) takes over.
+#[derive(Clone, Copy)]
+pub struct ScheduleSlot {
+    pub weekday: Weekday,
+    pub start_time: NaiveTime,
+    pub target_temp: f64,
+}
+
+impl fmt::Display for ScheduleSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} -> {}°C",
+            self.weekday,
+            self.start_time.format("%H:%M"),
+            self.target_temp
+        )
+    }
+}
+
+/// Parses a slot out of text like `"Mon 07:00 21.5"`.
+pub fn parse_slot(text: &str) -> Option<ScheduleSlot> {
+    let mut parts = text.split_whitespace();
+    let weekday = parse_weekday(parts.next()?)?;
+    let start_time = NaiveTime::parse_from_str(parts.next()?, "%H:%M").ok()?;
+    let target_temp = parts.next()?.parse::<f64>().ok()?;
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some(ScheduleSlot {
+        weekday,
+        start_time,
+        target_temp,
+    })
+}
+
+fn parse_weekday(text: &str) -> Option<Weekday> {
+    match text.to_lowercase().as_str() {
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Schedule {
+    pub slots: Vec<ScheduleSlot>,
+}
+
+impl Schedule {
+    fn minute_of_week(weekday: Weekday, time: NaiveTime) -> i64 {
+        weekday.num_days_from_monday() as i64 * 24 * 60
+            + time.hour() as i64 * 60
+            + time.minute() as i64
+    }
+
+    /// Returns the slot that is currently in effect: the one with the latest
+    /// start time at or before `now`, wrapping around to the last slot of
+    /// the previous week if every slot this week is still ahead of `now`.
+    pub fn active_slot(&self, now: DateTime<Local>) -> Option<&ScheduleSlot> {
+        let now_minute = Self::minute_of_week(now.weekday(), now.time());
+
+        self.slots
+            .iter()
+            .filter(|slot| Self::minute_of_week(slot.weekday, slot.start_time) <= now_minute)
+            .max_by_key(|slot| Self::minute_of_week(slot.weekday, slot.start_time))
+            .or_else(|| {
+                self.slots
+                    .iter()
+                    .max_by_key(|slot| Self::minute_of_week(slot.weekday, slot.start_time))
+            })
+    }
+
+    /// Returns when the currently active slot will give way to the next
+    /// one, so a manual override can be scheduled to expire then. With a
+    /// single slot, that's its own next weekly occurrence; with no slots at
+    /// all there's nothing to give way to, so the override should persist
+    /// indefinitely.
+    pub fn next_transition(&self, now: DateTime<Local>) -> Option<DateTime<Local>> {
+        if self.slots.is_empty() {
+            return None;
+        }
+
+        let now_minute = Self::minute_of_week(now.weekday(), now.time());
+
+        let next_minute = self
+            .slots
+            .iter()
+            .map(|slot| Self::minute_of_week(slot.weekday, slot.start_time))
+            .filter(|&minute| minute > now_minute)
+            .min()
+            .or_else(|| {
+                self.slots
+                    .iter()
+                    .map(|slot| Self::minute_of_week(slot.weekday, slot.start_time))
+                    .min()
+            })?;
+
+        let delta_minutes = if next_minute > now_minute {
+            next_minute - now_minute
+        } else {
+            next_minute + 7 * 24 * 60 - now_minute
+        };
+
+        Some(now + chrono::Duration::minutes(delta_minutes))
+    }
+}
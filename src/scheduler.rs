@@ -0,0 +1,48 @@
+use crate::HeatingState;
+use chrono::Local;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Once a minute, applies each zone's currently-active schedule slot to its
+/// `target_temp`, unless a manual `/settemp` override is still in effect for
+/// that zone. Also re-checks every zone's on/off decision on this same
+/// timer, independent of schedules or overrides, so a zone whose sensor
+/// stops posting still times out via `stale_after_secs` instead of keeping
+/// whatever `last_decision` it last computed forever.
+pub async fn run_scheduler(heating_state: Arc<Mutex<HeatingState>>) {
+    let mut ticker = tokio::time::interval(Duration::from_secs(60));
+
+    loop {
+        ticker.tick().await;
+
+        let now = Local::now();
+        let mut state = heating_state.lock().await;
+
+        let updates: Vec<(String, f64)> = state
+            .zones
+            .iter_mut()
+            .filter_map(|(name, zone)| {
+                if let Some(until) = zone.override_until {
+                    if now < until {
+                        return None;
+                    }
+                    zone.override_until = None;
+                }
+
+                zone.schedule
+                    .active_slot(now)
+                    .map(|slot| (name.clone(), slot.target_temp))
+            })
+            .collect();
+
+        for (zone, target_temp) in updates {
+            state.set_zone_target_temp(&zone, target_temp).await;
+        }
+
+        let zones: Vec<String> = state.zones.keys().cloned().collect();
+        for zone in zones {
+            state.update_zone_decision(&zone);
+        }
+    }
+}
@@ -1,19 +1,24 @@
-use crate::HeatingState;
+use crate::zone::Zone;
+use crate::{metrics, HeatingState};
 use axum::extract::{Path, State};
 use axum::routing::{get, post};
 use axum::Router;
 use log::info;
 use std::sync::Arc;
-use std::time::{Duration, SystemTime};
+use std::time::SystemTime;
 use tokio::sync::Mutex;
 
-pub const HEATING_IS_ON_ROUTE: &str = "/heating_is_on";
-pub const CURRENT_TEMP_ROUTE: &str = "/temp/:temp";
+pub const HEATING_IS_ON_ROUTE: &str = "/heating_is_on/:zone";
+pub const ANY_HEATING_IS_ON_ROUTE: &str = "/heating_is_on";
+pub const CURRENT_TEMP_ROUTE: &str = "/temp/:zone/:temp";
+pub const METRICS_ROUTE: &str = "/metrics";
 
 pub async fn start_server(port: String, heating_state: Arc<Mutex<HeatingState>>) {
     let app = Router::new()
+        .route(ANY_HEATING_IS_ON_ROUTE, get(any_heating_is_on))
         .route(HEATING_IS_ON_ROUTE, get(heating_is_on))
         .route(CURRENT_TEMP_ROUTE, post(receive_temp))
+        .route(METRICS_ROUTE, get(metrics_route))
         .with_state(heating_state);
 
     let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{port}"))
@@ -23,28 +28,43 @@ pub async fn start_server(port: String, heating_state: Arc<Mutex<HeatingState>>)
     info!("Listening on {}", listener.local_addr().unwrap());
     axum::serve(listener, app).await.unwrap();
 }
-async fn heating_is_on(State(heating_state): State<Arc<Mutex<HeatingState>>>) -> String {
+
+async fn heating_is_on(
+    State(heating_state): State<Arc<Mutex<HeatingState>>>,
+    Path(zone): Path<String>,
+) -> String {
     let heating_state = heating_state.lock().await;
 
-    if SystemTime::now()
-        .duration_since(heating_state.current_temp_reported_at)
-        .unwrap()
-        > Duration::from_secs(15 * 60)
-    {
-        return "false".into();
+    match heating_state.zones.get(&zone) {
+        Some(zone_state) => zone_state.heating_is_on().to_string(),
+        None => "false".into(),
     }
+}
 
-    match heating_state.heating_is_on && heating_state.current_temp < heating_state.target_temp {
-        true => "true".into(),
-        false => "false".into(),
-    }
+/// Whether the physical boiler relay should fire: true if *any* zone
+/// currently demands heat.
+async fn any_heating_is_on(State(heating_state): State<Arc<Mutex<HeatingState>>>) -> String {
+    let heating_state = heating_state.lock().await;
+
+    heating_state
+        .zones
+        .values()
+        .any(Zone::heating_is_on)
+        .to_string()
 }
 
 async fn receive_temp(
     State(heating_state): State<Arc<Mutex<HeatingState>>>,
-    Path(temp): Path<f64>,
+    Path((zone, temp)): Path<(String, f64)>,
 ) {
     let mut heating_state = heating_state.lock().await;
-    heating_state.current_temp = temp;
-    heating_state.current_temp_reported_at = SystemTime::now();
+    let zone_state = heating_state.zone_mut(&zone);
+    zone_state.current_temp = temp;
+    zone_state.current_temp_reported_at = SystemTime::now();
+    heating_state.update_zone_decision(&zone);
+}
+
+async fn metrics_route(State(heating_state): State<Arc<Mutex<HeatingState>>>) -> String {
+    let heating_state = heating_state.lock().await;
+    metrics::render(&heating_state)
 }
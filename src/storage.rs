@@ -0,0 +1,91 @@
+use log::error;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Persists the subset of each zone that should survive a restart
+/// (`target_temp` and `heating_switch_is_on`). Sensor readings are transient
+/// and are never written here.
+pub struct Storage {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl Storage {
+    pub async fn open(path: &str) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS zones (
+                name TEXT PRIMARY KEY,
+                target_temp REAL NOT NULL,
+                heating_switch_is_on INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            )",
+            [],
+        )?;
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    /// Returns the last persisted `(target_temp, heating_switch_is_on)` for
+    /// every zone the bot has ever saved, keyed by zone name.
+    pub async fn load_zones(&self) -> rusqlite::Result<HashMap<String, (f64, bool)>> {
+        let conn = self.conn.lock().await;
+        let mut stmt =
+            conn.prepare("SELECT name, target_temp, heating_switch_is_on FROM zones")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                (row.get::<_, f64>(1)?, row.get::<_, i64>(2)? != 0),
+            ))
+        })?;
+        rows.collect()
+    }
+
+    pub async fn save_zone(&self, name: &str, target_temp: f64, heating_switch_is_on: bool) {
+        let conn = self.conn.lock().await;
+        let result = conn.execute(
+            "INSERT INTO zones (name, target_temp, heating_switch_is_on) VALUES (?1, ?2, ?3)
+             ON CONFLICT(name) DO UPDATE SET
+                target_temp = excluded.target_temp,
+                heating_switch_is_on = excluded.heating_switch_is_on",
+            params![name, target_temp, heating_switch_is_on as i64],
+        );
+
+        if let Err(e) = result {
+            error!("Failed to persist zone \"{name}\": {e}");
+        }
+    }
+
+    /// Returns the last persisted value for `key`, e.g. `"authorized_users"`
+    /// or `"stale_after_secs"`, if it was ever saved.
+    pub async fn load_setting(&self, key: &str) -> rusqlite::Result<Option<String>> {
+        let conn = self.conn.lock().await;
+        conn.query_row(
+            "SELECT value FROM settings WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        )
+        .optional()
+    }
+
+    pub async fn save_setting(&self, key: &str, value: &str) {
+        let conn = self.conn.lock().await;
+        let result = conn.execute(
+            "INSERT INTO settings (key, value) VALUES (?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![key, value],
+        );
+
+        if let Err(e) = result {
+            error!("Failed to persist setting \"{key}\": {e}");
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::schedule::Schedule;
+use chrono::{DateTime, Local};
+use std::time::{Duration, SystemTime};
+
+/// Per-room heating state: its own sensor reading, setpoint, schedule and
+/// switch. The boiler itself should fire whenever any zone demands heat.
+pub struct Zone {
+    pub target_temp: f64,
+    pub current_temp: f64,
+    pub current_temp_reported_at: SystemTime,
+    pub heating_switch_is_on: bool,
+    pub schedule: Schedule,
+    pub override_until: Option<DateTime<Local>>,
+    last_decision: bool,
+    heating_on_since: Option<SystemTime>,
+    cumulative_heating_secs: f64,
+}
+
+impl Zone {
+    pub fn new(target_temp: f64) -> Self {
+        Self {
+            target_temp,
+            current_temp: 0.0,
+            current_temp_reported_at: SystemTime::now(),
+            heating_switch_is_on: false,
+            schedule: Schedule::default(),
+            override_until: None,
+            last_decision: false,
+            heating_on_since: None,
+            cumulative_heating_secs: 0.0,
+        }
+    }
+
+    /// The last on/off decision computed by `update_decision`. The boiler
+    /// itself should fire whenever this is true for *any* zone.
+    pub fn heating_is_on(&self) -> bool {
+        self.last_decision
+    }
+
+    /// Total seconds this zone's heating has been commanded on, including
+    /// the period still in progress if it's on right now.
+    pub fn heating_runtime_secs(&self) -> f64 {
+        let ongoing = self
+            .heating_on_since
+            .and_then(|since| SystemTime::now().duration_since(since).ok())
+            .map(|d| d.as_secs_f64())
+            .unwrap_or(0.0);
+
+        self.cumulative_heating_secs + ongoing
+    }
+
+    /// Recomputes the on/off decision with a `band`-wide hysteresis around
+    /// `target_temp`, so a reading that hovers at the setpoint doesn't flap
+    /// the decision on every update: once on, it stays on until
+    /// `current_temp >= target_temp + band/2`; once off, it stays off until
+    /// `current_temp <= target_temp - band/2`. A reading older than
+    /// `stale_after_secs` is treated as no longer demanding heat. Call this
+    /// whenever `current_temp`, `target_temp` or `heating_switch_is_on`
+    /// changes.
+    pub fn update_decision(&mut self, band: f64, stale_after_secs: u64) {
+        let was_on = self.last_decision;
+
+        let is_stale = SystemTime::now()
+            .duration_since(self.current_temp_reported_at)
+            .unwrap_or_default()
+            > Duration::from_secs(stale_after_secs);
+
+        if is_stale || !self.heating_switch_is_on {
+            self.last_decision = false;
+        } else {
+            let half_band = band / 2.0;
+            self.last_decision = if was_on {
+                self.current_temp < self.target_temp + half_band
+            } else {
+                self.current_temp <= self.target_temp - half_band
+            };
+        }
+
+        self.record_transition(was_on);
+    }
+
+    /// Accumulates runtime whenever the decision transitions from on to off,
+    /// and starts the clock on a transition from off to on.
+    fn record_transition(&mut self, was_on: bool) {
+        match (was_on, self.last_decision) {
+            (false, true) => self.heating_on_since = Some(SystemTime::now()),
+            (true, false) => {
+                if let Some(since) = self.heating_on_since.take() {
+                    self.cumulative_heating_secs += SystemTime::now()
+                        .duration_since(since)
+                        .unwrap_or_default()
+                        .as_secs_f64();
+                }
+            }
+            _ => {}
+        }
+    }
+}